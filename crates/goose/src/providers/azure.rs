@@ -1,19 +1,25 @@
 use anyhow::Result;
+use async_stream::try_stream;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use reqwest::Client;
 use serde_json::Value;
+use std::sync::Arc;
 use std::time::Duration;
 use serde::Serialize;
 use tokio::time::sleep;
 
 use super::azureauth::AzureAuth;
-use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::base::{CompletionEvent, ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
 use super::errors::ProviderError;
 use super::formats::openai::{create_request, get_usage, response_to_message};
+use super::redact;
+use super::retry_bucket::{RetryTokenBucket, RATE_LIMIT_RETRY_COST, TIMEOUT_RETRY_COST};
 use super::utils::{emit_debug_trace, get_model, handle_response_openai_compat, ImageFormat};
 use crate::message::Message;
 use crate::model::ModelConfig;
-use mcp_core::tool::Tool;
+use mcp_core::tool::{Tool, ToolCall};
 
 pub const AZURE_DEFAULT_MODEL: &str = "gpt-4o";
 pub const AZURE_DOC_URL: &str =
@@ -27,6 +33,10 @@ const DEFAULT_INITIAL_RETRY_INTERVAL_MS: u64 = 1000; // Start with 1 second
 const DEFAULT_MAX_RETRY_INTERVAL_MS: u64 = 32000; // Max 32 seconds
 const DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
 
+// Shared retry budget across every request this provider instance issues, so a broad
+// outage can't cause every concurrent caller to retry independently.
+const DEFAULT_RETRY_BUCKET_CAPACITY: u32 = 500;
+
 #[derive(Debug)]
 pub struct AzureProvider {
     client: Client,
@@ -34,6 +44,7 @@ pub struct AzureProvider {
     endpoint: String,
     deployment_name: String,
     api_version: String,
+    retry_bucket: Arc<RetryTokenBucket>,
 }
 
 impl Serialize for AzureProvider {
@@ -70,9 +81,24 @@ impl AzureProvider {
         let api_key = config.get_secret("AZURE_OPENAI_API_KEY").ok();
         let auth = AzureAuth::new(api_key)?;
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(600))
-            .build()?;
+        let mut client_builder = Client::builder().timeout(Duration::from_secs(600));
+
+        let proxy_url = config
+            .get_param("AZURE_OPENAI_PROXY")
+            .ok()
+            .or_else(|| std::env::var("HTTPS_PROXY").ok())
+            .or_else(|| std::env::var("ALL_PROXY").ok());
+        if let Some(proxy_url) = proxy_url {
+            client_builder = client_builder.proxy(reqwest::Proxy::all(proxy_url)?);
+        }
+
+        let connect_timeout_secs: Option<u64> =
+            config.get_param("AZURE_OPENAI_CONNECT_TIMEOUT").ok();
+        if let Some(connect_timeout_secs) = connect_timeout_secs {
+            client_builder = client_builder.connect_timeout(Duration::from_secs(connect_timeout_secs));
+        }
+
+        let client = client_builder.build()?;
 
         Ok(Self {
             client,
@@ -80,6 +106,7 @@ impl AzureProvider {
             auth,
             deployment_name,
             api_version,
+            retry_bucket: Arc::new(RetryTokenBucket::new(DEFAULT_RETRY_BUCKET_CAPACITY)),
         })
     }
 
@@ -107,6 +134,9 @@ impl AzureProvider {
         let mut attempts = 0;
         let mut last_error = None;
         let mut current_delay = DEFAULT_INITIAL_RETRY_INTERVAL_MS;
+        // Tokens withdrawn from the shared retry bucket so far for this request; returned
+        // to the bucket if the request ultimately succeeds.
+        let mut retry_tokens_withdrawn = 0u32;
 
         loop {
             // Check if we've exceeded max retries
@@ -119,35 +149,22 @@ impl AzureProvider {
                 return Err(last_error.unwrap_or(ProviderError::RateLimitExceeded(error_msg)));
             }
 
-            // Get a fresh auth token for each attempt
-            let auth_token = self.auth.get_token().await.map_err(|e| {
+            // Resolve the auth header for this attempt. The cache inside `self.auth` only
+            // fetches a fresh token once the cached one is close to expiring, rather than
+            // on every attempt.
+            let auth_header = self.auth.header().await.map_err(|e| {
                 tracing::error!("Authentication error: {:?}", e);
                 ProviderError::RequestFailed(format!("Failed to get authentication token: {}", e))
             })?;
 
-            let mut request_builder = self.client.post(base_url.clone());
-            let token_value = auth_token.token_value.clone();
-
-            // Set the correct header based on authentication type
-            match self.auth.credential_type() {
-                super::azureauth::AzureCredentials::ApiKey(_) => {
-                    tracing::debug!("Using API key authentication");
-                    request_builder = request_builder.header("api-key", token_value.clone());
-                }
-                super::azureauth::AzureCredentials::DefaultCredential => {
-                    tracing::debug!("Using Azure default credential authentication");
-                    request_builder = request_builder.header(
-                        "Authorization",
-                        format!("Bearer {}", token_value.clone()),
-                    );
-                }
-            }
+            let request_builder = self
+                .client
+                .post(base_url.clone())
+                .header(auth_header.name.clone(), auth_header.value.clone());
 
             tracing::debug!(
-                "Sending request to Azure OpenAI (attempt {}): {} with payload: {:?}",
-                attempts + 1,
-                base_url,
-                payload
+                "Using {} authentication",
+                auth_header.name.as_str()
             );
 
             // Log request details before sending
@@ -163,42 +180,45 @@ impl AzureProvider {
                 self.auth.credential_type(),
             );
 
-            // Log the raw HTTP request details
-            tracing::warn!(
-                "Raw HTTP Request (attempt {}):\nMethod: POST\nURL: {}\nHeaders:\n    Content-Type: application/json\n    {}: {}\nPayload: {:#}",
-                attempts + 1,
-                base_url,
-                match self.auth.credential_type() {
-                    super::azureauth::AzureCredentials::ApiKey(_) => "api-key",
-                    super::azureauth::AzureCredentials::DefaultCredential => "Authorization",
-                },
-                match self.auth.credential_type() {
-                    super::azureauth::AzureCredentials::ApiKey(_) => token_value,
-                    super::azureauth::AzureCredentials::DefaultCredential => format!("Bearer {}", token_value),
-                },
-                serde_json::to_string_pretty(&payload).unwrap_or_else(|_| format!("{:?}", payload))
-            );
+            // Log the outgoing request. Credential header values and the full payload are
+            // only ever logged in the clear when GOOSE_LOG_RAW_REQUESTS is explicitly set;
+            // otherwise the header is redacted and the payload is elided.
+            if redact::raw_logging_enabled() {
+                tracing::warn!(
+                    "Raw HTTP Request (attempt {}):\nMethod: POST\nURL: {}\nHeaders:\n    Content-Type: application/json\n    {}: {}\nPayload: {:#}",
+                    attempts + 1,
+                    base_url,
+                    auth_header.name.as_str(),
+                    auth_header.value,
+                    serde_json::to_string_pretty(&payload).unwrap_or_else(|_| format!("{:?}", payload))
+                );
+            } else {
+                tracing::debug!(
+                    "Sending request (attempt {}): {} {}: {}",
+                    attempts + 1,
+                    base_url,
+                    auth_header.name.as_str(),
+                    redact::redact_credential(&auth_header.value)
+                );
+            }
 
             let response_result = request_builder.json(&payload).send().await;
-            
-            // Log the raw response result
-            tracing::warn!(
-                "Raw response result from Azure OpenAI (attempt {}): {:?}",
-                attempts + 1,
-                response_result
-            );
-            
+
             match response_result {
                 Ok(response) => {
                     let status = response.status();
                     let headers = response.headers().clone();
-                    
-                    tracing::warn!(
-                        "Raw response details:\nStatus: {}\nHeaders: {:?}\nResponse: {:?}",
-                        status,
-                        headers,
-                        response
-                    );
+
+                    if redact::raw_logging_enabled() {
+                        tracing::warn!(
+                            "Raw response details:\nStatus: {}\nHeaders: {:?}\nResponse: {:?}",
+                            status,
+                            headers,
+                            response
+                        );
+                    } else {
+                        tracing::debug!("Received response (attempt {}): {}", attempts + 1, status);
+                    }
 
                     match handle_response_openai_compat(response).await {
                         Ok(result) => {
@@ -207,9 +227,19 @@ impl AzureProvider {
                                 attempts + 1
                             );
                             tracing::debug!("Response content: {:?}", result);
+                            self.retry_bucket.refund(retry_tokens_withdrawn);
+                            self.retry_bucket.refill_on_success();
                             return Ok(result);
                         }
                         Err(ProviderError::RateLimitExceeded(msg)) => {
+                            if !self.retry_bucket.try_withdraw(RATE_LIMIT_RETRY_COST) {
+                                tracing::error!(
+                                    "Retry token bucket exhausted; failing rate-limited request immediately instead of retrying"
+                                );
+                                return Err(ProviderError::RateLimitExceeded(msg));
+                            }
+                            retry_tokens_withdrawn += RATE_LIMIT_RETRY_COST;
+
                             attempts += 1;
                             last_error = Some(ProviderError::RateLimitExceeded(msg.clone()));
 
@@ -220,22 +250,12 @@ impl AzureProvider {
                                 msg
                             );
 
-                            let retry_after = if let Some(secs) = msg.to_lowercase().find("try again in ") {
-                                msg[secs..]
-                                    .split_whitespace()
-                                    .nth(3)
-                                    .and_then(|s| s.parse::<u64>().ok())
-                                    .unwrap_or(0)
-                            } else {
-                                0
-                            };
-
-                            let delay = if retry_after > 0 {
+                            let delay = if let Some(retry_after) = retry_delay_from_headers(&headers) {
                                 tracing::info!(
-                                    "Using server-provided retry-after value: {} seconds",
+                                    "Using server-provided retry delay from response headers: {:?}",
                                     retry_after
                                 );
-                                Duration::from_secs(retry_after)
+                                retry_after
                             } else {
                                 let delay = current_delay.min(DEFAULT_MAX_RETRY_INTERVAL_MS);
                                 current_delay = (current_delay as f64 * DEFAULT_BACKOFF_MULTIPLIER) as u64;
@@ -281,6 +301,17 @@ impl AzureProvider {
                     
                     // For timeout errors, we should retry
                     if e.is_timeout() {
+                        if !self.retry_bucket.try_withdraw(TIMEOUT_RETRY_COST) {
+                            tracing::error!(
+                                "Retry token bucket exhausted; failing timed-out request immediately instead of retrying"
+                            );
+                            return Err(ProviderError::RequestFailed(format!(
+                                "Request timed out and retry budget is exhausted: {}",
+                                e
+                            )));
+                        }
+                        retry_tokens_withdrawn += TIMEOUT_RETRY_COST;
+
                         attempts += 1;
                         let delay = current_delay.min(DEFAULT_MAX_RETRY_INTERVAL_MS);
                         current_delay = (current_delay as f64 * DEFAULT_BACKOFF_MULTIPLIER) as u64;
@@ -301,6 +332,303 @@ impl AzureProvider {
             }
         }
     }
+
+    /// Issue a streaming completion request and decode the `text/event-stream` body into
+    /// a stream of [`CompletionEvent`]s, terminating on the `data: [DONE]` sentinel.
+    async fn post_stream(
+        &self,
+        mut payload: Value,
+    ) -> Result<BoxStream<'static, Result<CompletionEvent, ProviderError>>, ProviderError> {
+        payload["stream"] = Value::Bool(true);
+        payload["stream_options"] = serde_json::json!({ "include_usage": true });
+
+        let mut base_url = url::Url::parse(&self.endpoint)
+            .map_err(|e| ProviderError::RequestFailed(format!("Invalid base URL: {e}")))?;
+
+        let existing_path = base_url.path().trim_end_matches('/');
+        let new_path = format!(
+            "{}/openai/deployments/{}/chat/completions",
+            existing_path, self.deployment_name
+        );
+        base_url.set_path(&new_path);
+        base_url.set_query(Some(&format!("api-version={}", self.api_version)));
+
+        // Establishing the stream shares the same retry bucket and header-aware backoff as
+        // `post()`, so a broad outage gates SSE connection attempts the same way it gates
+        // blocking ones. Once the stream is open, individual SSE parse failures are not
+        // retried here — that would require replaying already-yielded deltas to the caller.
+        let mut attempts = 0;
+        let mut current_delay = DEFAULT_INITIAL_RETRY_INTERVAL_MS;
+        let mut retry_tokens_withdrawn = 0u32;
+
+        let response = loop {
+            if attempts > 0 && attempts > DEFAULT_MAX_RETRIES {
+                return Err(ProviderError::RequestFailed(format!(
+                    "Exceeded maximum retry attempts ({}) establishing a streaming connection",
+                    DEFAULT_MAX_RETRIES
+                )));
+            }
+
+            let auth_header = self.auth.header().await.map_err(|e| {
+                tracing::error!("Authentication error: {:?}", e);
+                ProviderError::RequestFailed(format!("Failed to get authentication token: {}", e))
+            })?;
+
+            let request_builder = self
+                .client
+                .post(base_url.clone())
+                .header(auth_header.name, auth_header.value);
+
+            match request_builder.json(&payload).send().await {
+                Ok(response) if response.status().is_success() => {
+                    self.retry_bucket.refund(retry_tokens_withdrawn);
+                    self.retry_bucket.refill_on_success();
+                    break response;
+                }
+                Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    let headers = response.headers().clone();
+                    let body = response.text().await.unwrap_or_default();
+
+                    if !self.retry_bucket.try_withdraw(RATE_LIMIT_RETRY_COST) {
+                        tracing::error!(
+                            "Retry token bucket exhausted; failing rate-limited stream request immediately instead of retrying"
+                        );
+                        return Err(ProviderError::RateLimitExceeded(body));
+                    }
+                    retry_tokens_withdrawn += RATE_LIMIT_RETRY_COST;
+                    attempts += 1;
+
+                    let delay = retry_delay_from_headers(&headers).unwrap_or_else(|| {
+                        let delay = current_delay.min(DEFAULT_MAX_RETRY_INTERVAL_MS);
+                        current_delay = (current_delay as f64 * DEFAULT_BACKOFF_MULTIPLIER) as u64;
+                        Duration::from_millis(delay)
+                    });
+
+                    tracing::warn!(
+                        "Rate limited while establishing stream (attempt {}/{}). Retrying after {:?}...",
+                        attempts,
+                        DEFAULT_MAX_RETRIES,
+                        delay
+                    );
+                    sleep(delay).await;
+                    continue;
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(ProviderError::RequestFailed(format!(
+                        "Azure OpenAI returned {status}: {body}"
+                    )));
+                }
+                Err(e) if e.is_timeout() => {
+                    if !self.retry_bucket.try_withdraw(TIMEOUT_RETRY_COST) {
+                        tracing::error!(
+                            "Retry token bucket exhausted; failing timed-out stream request immediately instead of retrying"
+                        );
+                        return Err(ProviderError::RequestFailed(format!(
+                            "Request timed out and retry budget is exhausted: {}",
+                            e
+                        )));
+                    }
+                    retry_tokens_withdrawn += TIMEOUT_RETRY_COST;
+                    attempts += 1;
+
+                    let delay = current_delay.min(DEFAULT_MAX_RETRY_INTERVAL_MS);
+                    current_delay = (current_delay as f64 * DEFAULT_BACKOFF_MULTIPLIER) as u64;
+
+                    tracing::warn!(
+                        "Stream request timeout (attempt {}/{}). Retrying after {} ms...",
+                        attempts,
+                        DEFAULT_MAX_RETRIES,
+                        delay
+                    );
+                    sleep(Duration::from_millis(delay)).await;
+                    continue;
+                }
+                Err(e) => {
+                    return Err(ProviderError::RequestFailed(format!("Request failed: {}", e)));
+                }
+            }
+        };
+
+        let mut byte_stream = response.bytes_stream();
+
+        let stream = try_stream! {
+            let mut buffer = String::new();
+            let mut tool_calls: std::collections::HashMap<u64, PartialToolCall> = std::collections::HashMap::new();
+
+            while let Some(chunk) = byte_stream.next().await {
+                let chunk = chunk.map_err(|e| ProviderError::RequestFailed(format!("Stream read failed: {}", e)))?;
+                buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+                while let Some(newline_pos) = buffer.find('\n') {
+                    let line = buffer[..newline_pos].trim_end_matches('\r').to_string();
+                    buffer.drain(..=newline_pos);
+
+                    let Some(data) = line.strip_prefix("data: ") else { continue };
+                    if data == "[DONE]" {
+                        return;
+                    }
+                    if data.is_empty() {
+                        continue;
+                    }
+
+                    let event: Value = serde_json::from_str(data)
+                        .map_err(|e| ProviderError::RequestFailed(format!("Invalid SSE chunk: {}", e)))?;
+
+                    if let Some(usage) = event.get("usage").filter(|u| !u.is_null()) {
+                        let model = get_model(&event);
+                        if let Ok(usage) = get_usage(&serde_json::json!({ "usage": usage })) {
+                            yield CompletionEvent::Usage(ProviderUsage::new(model, usage));
+                        }
+                    }
+
+                    let Some(delta) = event["choices"][0].get("delta") else { continue };
+
+                    if let Some(message) = delta_to_message(delta) {
+                        yield CompletionEvent::Delta(message);
+                    }
+
+                    if let Some(fragments) = delta.get("tool_calls").and_then(Value::as_array) {
+                        for fragment in fragments {
+                            let Some(index) = fragment.get("index").and_then(Value::as_u64) else { continue };
+                            let partial = tool_calls.entry(index).or_default();
+                            partial.merge(fragment);
+
+                            if let Some(message) = partial.take_message() {
+                                yield CompletionEvent::Delta(message);
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Derive how long to wait before retrying from the response headers, preferring the
+/// standard `Retry-After` header and falling back to Azure's `x-ratelimit-reset-*` headers.
+/// Returns `None` when no header carries a usable value, in which case the caller should
+/// fall back to exponential backoff.
+fn retry_delay_from_headers(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    if let Some(value) = headers.get(reqwest::header::RETRY_AFTER).and_then(|v| v.to_str().ok()) {
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        if let Ok(date) = httpdate::parse_http_date(value) {
+            if let Ok(remaining) = date.duration_since(std::time::SystemTime::now()) {
+                return Some(remaining);
+            }
+        }
+    }
+
+    for header_name in ["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"] {
+        if let Some(value) = headers.get(header_name).and_then(|v| v.to_str().ok()) {
+            if let Some(delay) = parse_azure_reset_duration(value) {
+                return Some(delay);
+            }
+        }
+    }
+
+    None
+}
+
+/// Parse Azure's `x-ratelimit-reset-*` duration strings (e.g. `"21s"`, `"1m6s"`, `"900ms"`)
+/// into a [`Duration`].
+fn parse_azure_reset_duration(value: &str) -> Option<Duration> {
+    let mut remainder = value.trim();
+    let mut total = Duration::ZERO;
+    let mut saw_component = false;
+
+    while !remainder.is_empty() {
+        let digits_end = remainder
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(remainder.len());
+        if digits_end == 0 {
+            return None;
+        }
+        let amount: f64 = remainder[..digits_end].parse().ok()?;
+        remainder = &remainder[digits_end..];
+
+        let (unit_len, seconds_per_unit) = if let Some(rest) = remainder.strip_prefix("ms") {
+            (remainder.len() - rest.len(), 0.001)
+        } else if let Some(rest) = remainder.strip_prefix('s') {
+            (remainder.len() - rest.len(), 1.0)
+        } else if let Some(rest) = remainder.strip_prefix('m') {
+            (remainder.len() - rest.len(), 60.0)
+        } else {
+            return None;
+        };
+
+        total += Duration::from_secs_f64(amount * seconds_per_unit);
+        remainder = &remainder[unit_len..];
+        saw_component = true;
+    }
+
+    saw_component.then_some(total)
+}
+
+/// Decode a single `choices[].delta` fragment from an SSE chunk into a partial [`Message`],
+/// skipping deltas (e.g. role-only or tool-call-only chunks) that carry no renderable text.
+fn delta_to_message(delta: &Value) -> Option<Message> {
+    if let Some(content) = delta.get("content").and_then(Value::as_str) {
+        if !content.is_empty() {
+            return Some(Message::assistant().with_text(content));
+        }
+    }
+    None
+}
+
+/// A tool call as it is progressively assembled from `choices[].delta.tool_calls[]`
+/// fragments: the `id` and `function.name` typically arrive whole in the first fragment
+/// for a given `index`, while `function.arguments` streams in piecemeal and is only valid
+/// JSON once every fragment has been appended.
+#[derive(Default)]
+struct PartialToolCall {
+    id: Option<String>,
+    name: Option<String>,
+    arguments: String,
+    /// Set once a [`Message`] has been emitted for this index, so a later fragment that
+    /// merely appends more bytes after an already-valid arguments string (or a stray
+    /// trailing chunk) can't cause the same tool call to be yielded — and potentially
+    /// executed — twice.
+    emitted: bool,
+}
+
+impl PartialToolCall {
+    /// Fold one `tool_calls[]` fragment into this accumulator.
+    fn merge(&mut self, fragment: &Value) {
+        if let Some(id) = fragment.get("id").and_then(Value::as_str) {
+            self.id = Some(id.to_string());
+        }
+        if let Some(function) = fragment.get("function") {
+            if let Some(name) = function.get("name").and_then(Value::as_str) {
+                self.name = Some(name.to_string());
+            }
+            if let Some(arguments) = function.get("arguments").and_then(Value::as_str) {
+                self.arguments.push_str(arguments);
+            }
+        }
+    }
+
+    /// Render the tool call as a [`Message`] the first time `id`, `name`, and a
+    /// fully-formed JSON arguments object are all available, and never again afterward —
+    /// earlier fragments are accumulated silently since a half-streamed arguments string
+    /// isn't a usable tool call yet, and later ones are ignored once it's already been
+    /// emitted once.
+    fn take_message(&mut self) -> Option<Message> {
+        if self.emitted {
+            return None;
+        }
+        let id = self.id.as_deref()?;
+        let name = self.name.as_deref()?;
+        let arguments: Value = serde_json::from_str(&self.arguments).ok()?;
+        let message = Message::assistant().with_tool_request(id, Ok(ToolCall::new(name, arguments)));
+        self.emitted = true;
+        Some(message)
+    }
 }
 
 #[async_trait]
@@ -321,6 +649,11 @@ impl Provider for AzureProvider {
                 ConfigKey::new("AZURE_OPENAI_ENDPOINT", true, false, None),
                 ConfigKey::new("AZURE_OPENAI_DEPLOYMENT_NAME", true, false, None),
                 ConfigKey::new("AZURE_OPENAI_API_VERSION", true, false, Some("2024-10-21")),
+                ConfigKey::new("AZURE_OPENAI_PROXY", false, false, None),
+                ConfigKey::new("AZURE_OPENAI_CONNECT_TIMEOUT", false, false, None),
+                ConfigKey::new("AZURE_OPENAI_USE_MANAGED_IDENTITY", false, false, Some("false")),
+                ConfigKey::new("AZURE_OPENAI_MANAGED_IDENTITY_CLIENT_ID", false, false, None),
+                ConfigKey::new("GOOSE_LOG_RAW_REQUESTS", false, false, Some("false")),
             ],
         )
     }
@@ -357,8 +690,146 @@ impl Provider for AzureProvider {
         };
         tracing::info!("AzureProvider::complete: Usage extracted");
         let model = get_model(&response);
-        emit_debug_trace(&self.get_model_config(), &payload, &response, &usage);
+        // Message content is elided from the payload/response handed to the trace sink
+        // unless GOOSE_LOG_RAW_REQUESTS is set; the trace feature itself stays on for
+        // everyone.
+        emit_debug_trace(
+            &self.get_model_config(),
+            &redact::redact_payload_for_trace(&payload),
+            &redact::redact_payload_for_trace(&response),
+            &usage,
+        );
         tracing::info!("AzureProvider::complete: Returning Ok");
         Ok((message, ProviderUsage::new(model, usage)))
     }
+
+    async fn complete_stream(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<BoxStream<'static, Result<CompletionEvent, ProviderError>>, ProviderError> {
+        let payload = create_request(&self.get_model_config(), system, messages, tools, &ImageFormat::OpenAi)?;
+        self.post_stream(payload).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn parses_plain_seconds() {
+        assert_eq!(parse_azure_reset_duration("21s"), Some(Duration::from_secs(21)));
+    }
+
+    #[test]
+    fn parses_minutes_and_seconds() {
+        assert_eq!(
+            parse_azure_reset_duration("1m6s"),
+            Some(Duration::from_secs(66))
+        );
+    }
+
+    #[test]
+    fn parses_milliseconds() {
+        assert_eq!(
+            parse_azure_reset_duration("900ms"),
+            Some(Duration::from_millis(900))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_duration() {
+        assert_eq!(parse_azure_reset_duration("soon"), None);
+        assert_eq!(parse_azure_reset_duration(""), None);
+    }
+
+    #[test]
+    fn prefers_retry_after_in_seconds_over_ratelimit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("30s"));
+
+        assert_eq!(retry_delay_from_headers(&headers), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn falls_back_to_ratelimit_reset_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("6s"));
+
+        assert_eq!(retry_delay_from_headers(&headers), Some(Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn an_expired_retry_after_http_date_yields_no_usable_delay() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+
+        assert_eq!(retry_delay_from_headers(&headers), None);
+    }
+
+    #[test]
+    fn no_relevant_headers_yields_none() {
+        let headers = HeaderMap::new();
+        assert_eq!(retry_delay_from_headers(&headers), None);
+    }
+
+    #[test]
+    fn delta_with_empty_content_yields_no_message() {
+        let delta = serde_json::json!({ "content": "" });
+        assert!(delta_to_message(&delta).is_none());
+    }
+
+    #[test]
+    fn role_only_delta_yields_no_message() {
+        let delta = serde_json::json!({ "role": "assistant" });
+        assert!(delta_to_message(&delta).is_none());
+    }
+
+    #[test]
+    fn tool_call_fragment_yields_no_message_until_arguments_are_valid_json() {
+        let mut partial = PartialToolCall::default();
+        partial.merge(&serde_json::json!({
+            "id": "call_1",
+            "function": { "name": "search", "arguments": "{\"query\": " }
+        }));
+        assert!(partial.take_message().is_none());
+
+        partial.merge(&serde_json::json!({
+            "function": { "arguments": "\"rust\"}" }
+        }));
+        assert!(partial.take_message().is_some());
+    }
+
+    #[test]
+    fn tool_call_fragment_missing_id_yields_no_message() {
+        let mut partial = PartialToolCall::default();
+        partial.merge(&serde_json::json!({
+            "function": { "name": "search", "arguments": "{}" }
+        }));
+        assert!(partial.take_message().is_none());
+    }
+
+    #[test]
+    fn tool_call_is_never_emitted_more_than_once() {
+        let mut partial = PartialToolCall::default();
+        partial.merge(&serde_json::json!({
+            "id": "call_1",
+            "function": { "name": "search", "arguments": "{\"query\": \"rust\"}" }
+        }));
+        assert!(partial.take_message().is_some());
+
+        // A trailing fragment that lands after the arguments already parsed (extra bytes,
+        // or a stray repeat) must not cause a second message for the same tool call.
+        partial.merge(&serde_json::json!({
+            "function": { "arguments": "" }
+        }));
+        assert!(partial.take_message().is_none());
+    }
 }
@@ -0,0 +1,265 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::header::HeaderName;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+
+/// Refresh a cached token this far ahead of its actual expiry, so a request never races a
+/// token that's about to lapse mid-flight.
+const REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+const MANAGED_IDENTITY_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token?resource=https://cognitiveservices.azure.com";
+const COGNITIVE_SERVICES_SCOPE: &str = "https://cognitiveservices.azure.com/.default";
+
+/// The header `post()` should attach to the outgoing request for this attempt.
+#[derive(Debug, Clone)]
+pub struct AuthHeader {
+    pub name: HeaderName,
+    pub value: String,
+}
+
+/// A source of Azure OpenAI credentials. Implementations own how a token is obtained;
+/// caching (if any) lives inside them, so `AzureAuth::header()` is cheap to call on every
+/// attempt without re-fetching on each retry.
+#[async_trait]
+pub trait AzureCredentialProvider: std::fmt::Debug + Send + Sync {
+    async fn header(&self) -> Result<AuthHeader>;
+}
+
+/// Static API key credential, sent as the `api-key` header.
+struct ApiKeyCredential {
+    api_key: String,
+}
+
+/// Hand-written for the same reason as `AzureCredentials`'s impl: `AzureAuth` (and so the
+/// provider) prints this via `{:?}`, and the derived impl would show the key in full.
+impl std::fmt::Debug for ApiKeyCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyCredential")
+            .field("api_key", &super::redact::redact_credential(&self.api_key))
+            .finish()
+    }
+}
+
+#[async_trait]
+impl AzureCredentialProvider for ApiKeyCredential {
+    async fn header(&self) -> Result<AuthHeader> {
+        Ok(AuthHeader {
+            name: HeaderName::from_static("api-key"),
+            value: self.api_key.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: SystemTime,
+}
+
+/// Caches a bearer token until shortly before it expires, so credential providers only
+/// pay the cost of a token fetch once per lifetime rather than once per attempt.
+#[derive(Debug, Default)]
+struct TokenCache {
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl TokenCache {
+    /// Returns the cached token if it's still fresh, otherwise fetches a new one.
+    ///
+    /// The lock is held across `fetch` (not just the cache check), so concurrent callers
+    /// racing an expired token single-flight onto one refresh instead of each issuing their
+    /// own IMDS/AAD call and clobbering the cache with their own result — the whole point of
+    /// caching is to fetch once per lifetime, not once per waiter.
+    async fn get_or_refresh<F, Fut>(&self, fetch: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<CachedToken>>,
+    {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at > SystemTime::now() + REFRESH_SKEW {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let fresh = fetch().await?;
+        let token = fresh.token.clone();
+        *cached = Some(fresh);
+        Ok(token)
+    }
+}
+
+/// The Azure AD default credential chain (environment, managed identity, Azure CLI, ...),
+/// cached until shortly before expiry rather than re-resolved on every attempt.
+#[derive(Debug)]
+struct DefaultAzureCredential {
+    cache: TokenCache,
+}
+
+#[async_trait]
+impl AzureCredentialProvider for DefaultAzureCredential {
+    async fn header(&self) -> Result<AuthHeader> {
+        let token = self
+            .cache
+            .get_or_refresh(|| async move {
+                let credential = azure_identity::create_default_credential()?;
+                let token = credential.get_token(&[COGNITIVE_SERVICES_SCOPE]).await?;
+                Ok(CachedToken {
+                    token: token.token.secret().to_string(),
+                    expires_at: token.expires_on.into(),
+                })
+            })
+            .await?;
+
+        Ok(AuthHeader {
+            name: HeaderName::from_static("authorization"),
+            value: format!("Bearer {}", token),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct ImdsTokenResponse {
+    access_token: String,
+    expires_on: String,
+}
+
+/// Fetches tokens from the Azure Instance Metadata Service, so goose can authenticate from
+/// an Azure VM or AKS node via managed identity without storing any secret. System-assigned
+/// identity works out of the box; user-assigned identity requires `client_id` to be set via
+/// `AZURE_OPENAI_MANAGED_IDENTITY_CLIENT_ID` so IMDS knows which identity to mint a token
+/// for.
+#[derive(Debug)]
+struct ManagedIdentityCredential {
+    client: Client,
+    client_id: Option<String>,
+    cache: TokenCache,
+}
+
+#[async_trait]
+impl AzureCredentialProvider for ManagedIdentityCredential {
+    async fn header(&self) -> Result<AuthHeader> {
+        let client = self.client.clone();
+        let client_id = self.client_id.clone();
+        let token = self
+            .cache
+            .get_or_refresh(|| async move {
+                let mut request = client
+                    .get(MANAGED_IDENTITY_ENDPOINT)
+                    .header("Metadata", "true");
+                if let Some(client_id) = client_id {
+                    request = request.query(&[("client_id", client_id)]);
+                }
+
+                let response: ImdsTokenResponse = request
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+
+                let expires_at = response
+                    .expires_on
+                    .parse::<u64>()
+                    .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                    .unwrap_or_else(|_| SystemTime::now() + Duration::from_secs(300));
+
+                Ok(CachedToken {
+                    token: response.access_token,
+                    expires_at,
+                })
+            })
+            .await?;
+
+        Ok(AuthHeader {
+            name: HeaderName::from_static("authorization"),
+            value: format!("Bearer {}", token),
+        })
+    }
+}
+
+/// Which kind of credential backs this provider, kept for diagnostics (e.g. the `Auth
+/// Type:` debug log in `AzureProvider::post`).
+#[derive(Clone)]
+pub enum AzureCredentials {
+    ApiKey(String),
+    DefaultCredential,
+    ManagedIdentity,
+}
+
+/// Hand-written so the raw API key can never end up in a log line via `{:?}` — the derived
+/// impl would print `ApiKey(String)`'s contents in full.
+impl std::fmt::Debug for AzureCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ApiKey(key) => f
+                .debug_tuple("ApiKey")
+                .field(&super::redact::redact_credential(key))
+                .finish(),
+            Self::DefaultCredential => write!(f, "DefaultCredential"),
+            Self::ManagedIdentity => write!(f, "ManagedIdentity"),
+        }
+    }
+}
+
+/// Resolves and caches Azure OpenAI auth behind a single `header()` call, hiding the
+/// difference between a static API key, the Azure AD default credential chain, and
+/// IMDS-based managed identity.
+#[derive(Debug)]
+pub struct AzureAuth {
+    credential_type: AzureCredentials,
+    provider: Arc<dyn AzureCredentialProvider>,
+}
+
+impl AzureAuth {
+    pub fn new(api_key: Option<String>) -> Result<Self> {
+        if let Some(api_key) = api_key {
+            return Ok(Self {
+                credential_type: AzureCredentials::ApiKey(api_key.clone()),
+                provider: Arc::new(ApiKeyCredential { api_key }),
+            });
+        }
+
+        let config = crate::config::Config::global();
+        let use_managed_identity = config
+            .get_param::<String>("AZURE_OPENAI_USE_MANAGED_IDENTITY")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        if use_managed_identity {
+            let client_id = config
+                .get_param::<String>("AZURE_OPENAI_MANAGED_IDENTITY_CLIENT_ID")
+                .ok();
+
+            return Ok(Self {
+                credential_type: AzureCredentials::ManagedIdentity,
+                provider: Arc::new(ManagedIdentityCredential {
+                    client: Client::new(),
+                    client_id,
+                    cache: TokenCache::default(),
+                }),
+            });
+        }
+
+        Ok(Self {
+            credential_type: AzureCredentials::DefaultCredential,
+            provider: Arc::new(DefaultAzureCredential {
+                cache: TokenCache::default(),
+            }),
+        })
+    }
+
+    pub fn credential_type(&self) -> &AzureCredentials {
+        &self.credential_type
+    }
+
+    /// Resolve the header this attempt should send, refreshing the underlying token only
+    /// once it's close to expiry rather than on every call.
+    pub async fn header(&self) -> Result<AuthHeader> {
+        self.provider.header().await
+    }
+}
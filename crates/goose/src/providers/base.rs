@@ -0,0 +1,131 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::fmt::Debug;
+
+use super::errors::ProviderError;
+use crate::message::Message;
+use crate::model::ModelConfig;
+use mcp_core::tool::Tool;
+
+/// A single configuration value a provider needs from the user (an endpoint, a deployment
+/// name, a secret, ...), along with whether it's required, secret, and its default.
+#[derive(Debug, Clone)]
+pub struct ConfigKey {
+    pub name: String,
+    pub required: bool,
+    pub secret: bool,
+    pub default: Option<String>,
+}
+
+impl ConfigKey {
+    pub fn new(name: &str, required: bool, secret: bool, default: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            required,
+            secret,
+            default: default.map(|d| d.to_string()),
+        }
+    }
+}
+
+/// Static description of a provider: its id, display name, supported models, and the
+/// config keys it needs populated before it can be constructed.
+#[derive(Debug, Clone)]
+pub struct ProviderMetadata {
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub default_model: String,
+    pub known_models: Vec<String>,
+    pub doc_url: String,
+    pub config_keys: Vec<ConfigKey>,
+}
+
+impl ProviderMetadata {
+    pub fn new(
+        name: &str,
+        display_name: &str,
+        description: &str,
+        default_model: &str,
+        known_models: Vec<String>,
+        doc_url: &str,
+        config_keys: Vec<ConfigKey>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            display_name: display_name.to_string(),
+            description: description.to_string(),
+            default_model: default_model.to_string(),
+            known_models,
+            doc_url: doc_url.to_string(),
+            config_keys,
+        }
+    }
+}
+
+/// Token accounting for a single completion.
+#[derive(Debug, Clone, Default)]
+pub struct Usage {
+    pub input_tokens: Option<i32>,
+    pub output_tokens: Option<i32>,
+    pub total_tokens: Option<i32>,
+}
+
+/// Usage for a completion alongside the model that actually served it, since some
+/// providers (Azure OpenAI among them) resolve a deployment name to a concrete model only
+/// once the response comes back.
+#[derive(Debug, Clone)]
+pub struct ProviderUsage {
+    pub model: String,
+    pub usage: Usage,
+}
+
+impl ProviderUsage {
+    pub fn new(model: String, usage: Usage) -> Self {
+        Self { model, usage }
+    }
+}
+
+/// An incremental event produced while consuming a streaming completion.
+pub enum CompletionEvent {
+    /// A partial message fragment as it is decoded off the wire.
+    Delta(Message),
+    /// The usage totals carried by the final chunk, once available.
+    Usage(ProviderUsage),
+}
+
+/// A backend capable of turning a system prompt, message history, and tool definitions
+/// into a completion. Implemented once per LLM API (OpenAI, Azure OpenAI, Anthropic, ...).
+#[async_trait]
+pub trait Provider: Send + Sync + Debug {
+    fn metadata() -> ProviderMetadata
+    where
+        Self: Sized;
+
+    fn get_model_config(&self) -> ModelConfig;
+
+    async fn complete(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<(Message, ProviderUsage), ProviderError>;
+
+    /// Stream a completion, yielding incremental [`CompletionEvent`]s as they arrive
+    /// instead of blocking for the full response. Providers without native streaming
+    /// support fall back to a single blocking `complete()` call surfaced as a two-event
+    /// stream, so callers can always drive completions through this method.
+    async fn complete_stream(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<BoxStream<'static, Result<CompletionEvent, ProviderError>>, ProviderError> {
+        let (message, usage) = self.complete(system, messages, tools).await?;
+        Ok(Box::pin(futures::stream::iter([
+            Ok(CompletionEvent::Delta(message)),
+            Ok(CompletionEvent::Usage(usage)),
+        ])))
+    }
+}
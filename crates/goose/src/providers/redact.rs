@@ -0,0 +1,59 @@
+//! Keeps credentials and raw request/response bodies out of provider tracing by default.
+//!
+//! Every provider that logs outgoing headers or payloads at `warn`/`debug` level should
+//! route through [`redact_credential`] and gate full verbosity behind
+//! [`raw_logging_enabled`], so a credential header or message body is never written to
+//! logs unless the operator has explicitly opted in.
+
+/// Masks a credential header value for logging: a short prefix plus the total length, so
+/// log lines remain useful for correlating requests without leaking the secret itself.
+pub fn redact_credential(value: &str) -> String {
+    if value.is_empty() {
+        return "<empty>".to_string();
+    }
+    let prefix: String = value.chars().take(4).collect();
+    format!("{}***({} chars)", prefix, value.chars().count())
+}
+
+/// Whether full, unredacted request/response logging has been explicitly enabled via the
+/// `GOOSE_LOG_RAW_REQUESTS` config key. Defaults to `false`.
+pub fn raw_logging_enabled() -> bool {
+    crate::config::Config::global()
+        .get_param::<String>("GOOSE_LOG_RAW_REQUESTS")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+/// Clones `value` with every `content` field elided, unless raw logging is explicitly
+/// enabled. Intended for request/response payloads handed to a debug-trace sink, so the
+/// sink keeps working (shapes, token counts, model ids) without message text leaking into
+/// it by default.
+pub fn redact_payload_for_trace(value: &serde_json::Value) -> serde_json::Value {
+    if raw_logging_enabled() {
+        return value.clone();
+    }
+    elide_content(value.clone())
+}
+
+fn elide_content(mut value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match &mut value {
+        Value::Object(map) => {
+            if map.contains_key("content") {
+                map.insert("content".to_string(), Value::String("<redacted>".to_string()));
+            }
+            for (_, v) in map.iter_mut() {
+                let taken = std::mem::replace(v, Value::Null);
+                *v = elide_content(taken);
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                let taken = std::mem::replace(item, Value::Null);
+                *item = elide_content(taken);
+            }
+        }
+        _ => {}
+    }
+    value
+}
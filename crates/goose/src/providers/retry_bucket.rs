@@ -0,0 +1,108 @@
+use std::sync::Mutex;
+
+/// Cost, in tokens, of a retry attempt following a request timeout.
+pub const TIMEOUT_RETRY_COST: u32 = 5;
+/// Cost, in tokens, of a retry attempt following a throttling/429 response.
+pub const RATE_LIMIT_RETRY_COST: u32 = 10;
+/// Tokens granted back to the bucket whenever a request ultimately succeeds.
+const SUCCESS_REFILL: u32 = 1;
+
+/// Bounds the total retry load a provider issues during a sustained outage.
+///
+/// Without shared coordination, every concurrent request retries independently up to its
+/// own attempt limit, so a broad outage causes all of them to hammer the endpoint at once.
+/// `RetryTokenBucket` gates whether a retry is attempted at all on a shared token budget:
+/// each retry withdraws a cost up front and fails fast if the bucket is dry, while
+/// successful requests trickle tokens back in.
+#[derive(Debug)]
+pub struct RetryTokenBucket {
+    capacity: u32,
+    tokens: Mutex<u32>,
+}
+
+impl RetryTokenBucket {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            tokens: Mutex::new(capacity),
+        }
+    }
+
+    /// Withdraw `cost` tokens to fund a retry attempt. Returns `false` without modifying
+    /// the bucket if insufficient tokens remain, in which case the caller should give up
+    /// instead of retrying.
+    pub fn try_withdraw(&self, cost: u32) -> bool {
+        let mut tokens = self.tokens.lock().unwrap();
+        if *tokens >= cost {
+            *tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Return tokens previously withdrawn, e.g. once a retried request goes on to succeed.
+    pub fn refund(&self, amount: u32) {
+        if amount == 0 {
+            return;
+        }
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens = (*tokens + amount).min(self.capacity);
+    }
+
+    /// Small refill granted whenever a request succeeds, independent of any refund.
+    pub fn refill_on_success(&self) {
+        self.refund(SUCCESS_REFILL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdraws_when_tokens_available() {
+        let bucket = RetryTokenBucket::new(10);
+        assert!(bucket.try_withdraw(TIMEOUT_RETRY_COST));
+        assert_eq!(*bucket.tokens.lock().unwrap(), 10 - TIMEOUT_RETRY_COST);
+    }
+
+    #[test]
+    fn refuses_withdrawal_once_exhausted() {
+        let bucket = RetryTokenBucket::new(RATE_LIMIT_RETRY_COST - 1);
+        assert!(!bucket.try_withdraw(RATE_LIMIT_RETRY_COST));
+        assert_eq!(*bucket.tokens.lock().unwrap(), RATE_LIMIT_RETRY_COST - 1);
+    }
+
+    #[test]
+    fn refund_returns_withdrawn_tokens() {
+        let bucket = RetryTokenBucket::new(10);
+        assert!(bucket.try_withdraw(RATE_LIMIT_RETRY_COST));
+        bucket.refund(RATE_LIMIT_RETRY_COST);
+        assert_eq!(*bucket.tokens.lock().unwrap(), 10);
+    }
+
+    #[test]
+    fn refund_is_capped_at_capacity() {
+        let bucket = RetryTokenBucket::new(10);
+        bucket.refund(5);
+        assert_eq!(*bucket.tokens.lock().unwrap(), 10);
+    }
+
+    #[test]
+    fn refill_on_success_grants_a_small_amount() {
+        let bucket = RetryTokenBucket::new(10);
+        assert!(bucket.try_withdraw(RATE_LIMIT_RETRY_COST));
+        bucket.refill_on_success();
+        assert_eq!(*bucket.tokens.lock().unwrap(), 10 - RATE_LIMIT_RETRY_COST + SUCCESS_REFILL);
+    }
+
+    #[test]
+    fn a_retry_that_succeeds_can_fully_restore_the_bucket() {
+        let bucket = RetryTokenBucket::new(10);
+        assert!(bucket.try_withdraw(RATE_LIMIT_RETRY_COST));
+        bucket.refund(RATE_LIMIT_RETRY_COST);
+        bucket.refill_on_success();
+        assert_eq!(*bucket.tokens.lock().unwrap(), 10);
+    }
+}